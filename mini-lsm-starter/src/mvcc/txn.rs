@@ -29,6 +29,13 @@ pub struct Transaction {
     pub(crate) committed: Arc<AtomicBool>,
     /// Write set and read set
     pub(crate) key_hashes: Option<Mutex<(HashSet<u32>, HashSet<u32>)>>,
+    /// The ranges this txn scanned, recorded for phantom-safe validation.
+    ///
+    /// Tracked on the same serializable path as `key_hashes`: point reads catch
+    /// read-write conflicts on keys actually observed, while these ranges catch
+    /// phantoms — concurrent inserts into a range we scanned but never stepped
+    /// over.
+    pub(crate) read_ranges: Option<Mutex<Vec<(Bound<Bytes>, Bound<Bytes>)>>>,
 }
 
 impl Transaction {
@@ -52,6 +59,12 @@ impl Transaction {
     pub fn scan(self: &Arc<Self>, lower: Bound<&[u8]>, upper: Bound<&[u8]>) -> Result<TxnIterator> {
         self.abort_if_committed();
 
+        if let Some(read_ranges) = &self.read_ranges {
+            read_ranges
+                .lock()
+                .push((map_bytes_bound(lower), map_bytes_bound(upper)));
+        }
+
         let lsm_iter = self.inner.scan_with_ts(lower, upper, self.read_ts)?;
         let mut local_iter = TxnLocalIterator::new(
             Arc::clone(&self.local_storage),
@@ -110,8 +123,18 @@ impl Transaction {
         let commit_ts = self.inner.write_batch_inner(&record_batch)?;
 
         if let Some(key_hashes) = &self.key_hashes {
+            // Keep the actual written keys (not just their hashes) so later txns
+            // can test range-containment for phantom conflicts.
+            let write_set = record_batch
+                .iter()
+                .map(|record| match record {
+                    WriteBatchRecord::Put(key, _) => key.clone(),
+                    WriteBatchRecord::Del(key) => key.clone(),
+                })
+                .collect();
             let commit_data = CommittedTxnData {
                 key_hashes: key_hashes.lock().0.clone(),
+                write_set,
                 read_ts: self.read_ts,
                 commit_ts,
             };
@@ -151,9 +174,10 @@ impl Transaction {
             Bound::Excluded(expected_commit_ts),
         ));
 
+        let read_ranges = self.read_ranges.as_ref().map(|ranges| ranges.lock());
+
         for (_, earlier_txn) in earlier_txns {
-            println!("{:?}", earlier_txn.key_hashes);
-            // Abort if earlier-txn's write set intersects with our read set
+            // Abort if earlier-txn's write set intersects with our read set.
             if earlier_txn
                 .key_hashes
                 .intersection(&guard.1)
@@ -162,6 +186,18 @@ impl Transaction {
             {
                 return Err(anyhow!("Abort transaction"));
             }
+
+            // Abort on phantoms: an earlier committed write landed inside a range
+            // we scanned, even if we never stepped over that key.
+            if let Some(ranges) = &read_ranges {
+                if earlier_txn
+                    .write_set
+                    .iter()
+                    .any(|key| ranges.iter().any(|range| range_contains(range, key)))
+                {
+                    return Err(anyhow!("Abort transaction"));
+                }
+            }
         }
 
         Ok(())
@@ -175,6 +211,21 @@ impl Transaction {
     }
 }
 
+/// Test whether `key` falls inside a recorded scan range.
+fn range_contains(range: &(Bound<Bytes>, Bound<Bytes>), key: &Bytes) -> bool {
+    let lower_ok = match &range.0 {
+        Bound::Included(start) => key >= start,
+        Bound::Excluded(start) => key > start,
+        Bound::Unbounded => true,
+    };
+    let upper_ok = match &range.1 {
+        Bound::Included(end) => key <= end,
+        Bound::Excluded(end) => key < end,
+        Bound::Unbounded => true,
+    };
+    lower_ok && upper_ok
+}
+
 impl Drop for Transaction {
     fn drop(&mut self) {
         let mut ts = self.inner.mvcc().ts.lock();
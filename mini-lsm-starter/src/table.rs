@@ -12,8 +12,9 @@ use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
 pub use builder::SsTableBuilder;
-use bytes::{Buf, BufMut};
+use bytes::{Buf, BufMut, Bytes};
 pub use iterator::SsTableIterator;
+use memmap2::Mmap;
 
 use crate::block::Block;
 use crate::key::{KeyBytes, KeySlice};
@@ -21,6 +22,94 @@ use crate::lsm_storage::BlockCache;
 
 use self::bloom::Bloom;
 
+/// The block compression algorithm applied to every data block of an SST.
+///
+/// The chosen algorithm is persisted as a single byte in the SST footer so
+/// that `SsTable::open` can route `read_block` through the matching
+/// decompressor. Compression is a per-table option, mirroring mature LSM
+/// engines: cold tables can be compressed aggressively while hot tables stay
+/// uncompressed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CompressionType {
+    /// Blocks are stored verbatim.
+    #[default]
+    None,
+    Lz4,
+    Snappy,
+    Zstd,
+}
+
+impl CompressionType {
+    /// Encode the algorithm as the single footer byte.
+    pub(crate) fn encode(self) -> u8 {
+        match self {
+            CompressionType::None => 0,
+            CompressionType::Lz4 => 1,
+            CompressionType::Snappy => 2,
+            CompressionType::Zstd => 3,
+        }
+    }
+
+    /// Decode the footer byte back into an algorithm, erroring on unknown tags.
+    pub(crate) fn decode(byte: u8) -> Result<Self> {
+        match byte {
+            0 => Ok(CompressionType::None),
+            1 => Ok(CompressionType::Lz4),
+            2 => Ok(CompressionType::Snappy),
+            3 => Ok(CompressionType::Zstd),
+            other => Err(anyhow!("unknown SST compression type {other}")),
+        }
+    }
+
+    /// Compress a finalized data block before it is written to disk.
+    pub(crate) fn compress(self, data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            CompressionType::None => Ok(data.to_vec()),
+            CompressionType::Lz4 => Ok(lz4_flex::compress_prepend_size(data)),
+            CompressionType::Snappy => Ok(snap::raw::Encoder::new().compress_vec(data)?),
+            CompressionType::Zstd => Ok(zstd::encode_all(data, 0)?),
+        }
+    }
+
+    /// Decompress a data block read back from disk.
+    pub(crate) fn decompress(self, data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            CompressionType::None => Ok(data.to_vec()),
+            CompressionType::Lz4 => Ok(lz4_flex::decompress_size_prepended(data)?),
+            CompressionType::Snappy => Ok(snap::raw::Decoder::new().decompress_vec(data)?),
+            CompressionType::Zstd => Ok(zstd::decode_all(data)?),
+        }
+    }
+}
+
+/// Length of the shared prefix of two byte slices, used for front-coding.
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+/// Verify and strip the trailing 32-bit xxh3 checksum from a region read back
+/// from disk, returning the checksummed payload on success.
+///
+/// The checksum is appended by the builder after the region's bytes are
+/// finalized; verifying it here gives early, localized detection of bit-rot
+/// instead of a panic deep inside decoding.
+fn verify_checksum<'a>(buf: &'a [u8], context: impl std::fmt::Display) -> Result<&'a [u8]> {
+    let Some(split) = buf.len().checked_sub(std::mem::size_of::<u32>()) else {
+        return Err(anyhow!(
+            "region for {context} is too short to carry a checksum"
+        ));
+    };
+    let (data, mut checksum) = buf.split_at(split);
+    let expected = checksum.get_u32();
+    let actual = xxhash_rust::xxh3::xxh3_64(data) as u32;
+    if expected != actual {
+        return Err(anyhow!(
+            "checksum mismatch for {context}: expected {expected:#x}, got {actual:#x}"
+        ));
+    }
+    Ok(data)
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BlockMeta {
     /// Offset of this data block.
@@ -32,33 +121,87 @@ pub struct BlockMeta {
 }
 
 impl BlockMeta {
-    /// Encode block meta to a buffer.
-    /// You may add extra fields to the buffer,
-    /// in order to help keep track of `first_key` when decoding from the same buffer in the future.
+    /// The cadence at which a full `first_key` is emitted as a restart point.
+    /// The byte offsets of restart points are not recorded, so
+    /// `decode_block_meta` always decodes from the start of the buffer.
+    const RESTART_INTERVAL: usize = 16;
+
+    /// Encode block meta to a buffer using front-coding.
+    ///
+    /// Each `first_key` is stored as the length of the prefix shared with the
+    /// previous block's `first_key` plus only the differing suffix, with a full
+    /// key emitted every `RESTART_INTERVAL` entries as a restart point. Each
+    /// `last_key` is front-coded against its own block's `first_key`. A small
+    /// header carries the entry count and restart cadence so the decoder knows
+    /// where the restart points fall — the same restart-point technique
+    /// LevelDB-style block formats use, applied to the meta index.
     pub fn encode_block_meta(block_meta: &[BlockMeta], buf: &mut Vec<u8>) {
-        block_meta.iter().for_each(|meta| {
+        buf.put_u32(block_meta.len() as u32);
+        buf.put_u32(Self::RESTART_INTERVAL as u32);
+
+        let mut prev_first_key: &[u8] = &[];
+        for (idx, meta) in block_meta.iter().enumerate() {
             buf.put_u32(meta.offset as u32);
-            buf.put_u32(meta.first_key.len() as u32);
-            buf.put_slice(meta.first_key.raw_ref());
-            buf.put_u32(meta.last_key.len() as u32);
-            buf.put_slice(meta.last_key.raw_ref());
-        })
+
+            let first_key = meta.first_key.raw_ref();
+            if idx % Self::RESTART_INTERVAL == 0 {
+                // Restart point: emit the full key so decoding can resume here.
+                buf.put_u32(first_key.len() as u32);
+                buf.put_slice(first_key);
+            } else {
+                let shared = common_prefix_len(prev_first_key, first_key);
+                buf.put_u32(shared as u32);
+                buf.put_u32((first_key.len() - shared) as u32);
+                buf.put_slice(&first_key[shared..]);
+            }
+            prev_first_key = first_key;
+
+            let last_key = meta.last_key.raw_ref();
+            let shared = common_prefix_len(first_key, last_key);
+            buf.put_u32(shared as u32);
+            buf.put_u32((last_key.len() - shared) as u32);
+            buf.put_slice(&last_key[shared..]);
+        }
     }
 
-    /// Decode block meta from a buffer.
+    /// Decode block meta from a front-coded buffer (see `encode_block_meta`).
     pub fn decode_block_meta(mut buf: impl Buf) -> Vec<BlockMeta> {
-        let mut res = Vec::new();
+        let count = buf.get_u32() as usize;
+        let restart_interval = buf.get_u32() as usize;
+        let mut res = Vec::with_capacity(count);
 
-        while buf.remaining() > 0 {
+        let mut prev_first_key: Vec<u8> = Vec::new();
+        for idx in 0..count {
             let offset = buf.get_u32() as usize;
-            let first_key_len = buf.get_u32() as usize;
-            let first_key = KeyBytes::from_bytes(buf.copy_to_bytes(first_key_len));
-            let last_key_len = buf.get_u32() as usize;
-            let last_key = KeyBytes::from_bytes(buf.copy_to_bytes(last_key_len));
+
+            let first_key = if idx % restart_interval == 0 {
+                let len = buf.get_u32() as usize;
+                let mut key = vec![0u8; len];
+                buf.copy_to_slice(&mut key);
+                key
+            } else {
+                let shared = buf.get_u32() as usize;
+                let suffix_len = buf.get_u32() as usize;
+                let mut key = prev_first_key[..shared].to_vec();
+                let start = key.len();
+                key.resize(shared + suffix_len, 0);
+                buf.copy_to_slice(&mut key[start..]);
+                key
+            };
+            prev_first_key = first_key.clone();
+
+            // last_key was front-coded against this block's first_key.
+            let shared = buf.get_u32() as usize;
+            let suffix_len = buf.get_u32() as usize;
+            let mut last_key = first_key[..shared].to_vec();
+            let start = last_key.len();
+            last_key.resize(shared + suffix_len, 0);
+            buf.copy_to_slice(&mut last_key[start..]);
+
             res.push(BlockMeta {
                 offset,
-                first_key,
-                last_key,
+                first_key: KeyBytes::from_bytes(Bytes::from(first_key)),
+                last_key: KeyBytes::from_bytes(Bytes::from(last_key)),
             })
         }
 
@@ -67,10 +210,18 @@ impl BlockMeta {
 }
 
 /// A file object.
-pub struct FileObject(Option<File>, u64);
+///
+/// An SST may optionally be backed by a read-only memory map; when present,
+/// `read`/`read_slice` serve bytes straight out of the OS page cache without a
+/// `pread` and, for `read_slice`, without a heap allocation or memcpy.
+pub struct FileObject(Option<File>, u64, Option<Mmap>);
 
 impl FileObject {
     pub fn read(&self, offset: u64, len: u64) -> Result<Vec<u8>> {
+        if let Some(mmap) = self.2.as_ref() {
+            let start = offset as usize;
+            return Ok(mmap[start..start + len as usize].to_vec());
+        }
         use std::os::unix::fs::FileExt;
         let mut data = vec![0; len as usize];
         self.0
@@ -80,6 +231,20 @@ impl FileObject {
         Ok(data)
     }
 
+    /// Borrow a region of the file without copying.
+    ///
+    /// Only available for SSTs opened via [`FileObject::open_mmap`]; callers
+    /// that may face a file opened through `create`/`open` should use
+    /// [`FileObject::read`], which falls back to `pread`.
+    pub fn read_slice(&self, offset: u64, len: u64) -> Result<&[u8]> {
+        let mmap = self
+            .2
+            .as_ref()
+            .ok_or_else(|| anyhow!("read_slice requires a memory-mapped FileObject"))?;
+        let start = offset as usize;
+        Ok(&mmap[start..start + len as usize])
+    }
+
     pub fn size(&self) -> u64 {
         self.1
     }
@@ -91,13 +256,23 @@ impl FileObject {
         Ok(FileObject(
             Some(File::options().read(true).write(false).open(path)?),
             data.len() as u64,
+            None,
         ))
     }
 
     pub fn open(path: &Path) -> Result<Self> {
         let file = File::options().read(true).write(false).open(path)?;
         let size = file.metadata()?.len();
-        Ok(FileObject(Some(file), size))
+        Ok(FileObject(Some(file), size, None))
+    }
+
+    /// Open a file and back it with a read-only memory map for zero-copy reads.
+    pub fn open_mmap(path: &Path) -> Result<Self> {
+        let file = File::options().read(true).write(false).open(path)?;
+        let size = file.metadata()?.len();
+        // Safety: the SST is opened read-only and is never mutated in place.
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(FileObject(Some(file), size, Some(mmap)))
     }
 }
 
@@ -116,6 +291,8 @@ pub struct SsTable {
     pub(crate) bloom: Option<Bloom>,
     /// The maximum timestamp stored in this SST, implemented in week 3.
     max_ts: u64,
+    /// The compression algorithm applied to every data block of this SST.
+    compression: CompressionType,
 }
 
 impl SsTable {
@@ -127,19 +304,20 @@ impl SsTable {
     /// Open SSTable from a file.
     pub fn open(id: usize, block_cache: Option<Arc<BlockCache>>, file: FileObject) -> Result<Self> {
         let file_size = file.size();
-        // Step 1: Read bloom filter at the end
-        let bloom_offset = file.read(
-            file_size - std::mem::size_of::<u32>() as u64,
-            std::mem::size_of::<u32>() as u64,
-        )?;
+        // The footer trailer is the `bloom_offset` u32 followed by the single
+        // compression tag byte written at table-finalize time.
+        let compression =
+            CompressionType::decode(file.read(file_size - 1, 1)?.as_slice().get_u8())?;
+        let footer_len = std::mem::size_of::<u32>() as u64 + 1;
 
-        let bloom_offset = bloom_offset.as_slice().get_u32() as u64;
+        // Step 1: Read bloom filter, stored just before the footer trailer.
+        let bloom_offset = file
+            .read(file_size - footer_len, std::mem::size_of::<u32>() as u64)?
+            .as_slice()
+            .get_u32() as u64;
 
-        let bloom_buffer = file.read(
-            bloom_offset,
-            file_size - bloom_offset - std::mem::size_of::<u32>() as u64,
-        )?;
-        let bloom = Bloom::decode(&bloom_buffer)?;
+        let bloom_buffer = file.read(bloom_offset, file_size - bloom_offset - footer_len)?;
+        let bloom = Bloom::decode(verify_checksum(&bloom_buffer, "bloom filter")?)?;
 
         let block_meta_offset = file.read(
             bloom_offset - std::mem::size_of::<u32>() as u64,
@@ -150,7 +328,8 @@ impl SsTable {
             block_meta_offset,
             bloom_offset - block_meta_offset - std::mem::size_of::<u32>() as u64,
         )?;
-        let block_meta = BlockMeta::decode_block_meta(&block_meta_buffer[..]);
+        let block_meta =
+            BlockMeta::decode_block_meta(verify_checksum(&block_meta_buffer, "block meta")?);
         let first_key = block_meta.first().unwrap().first_key.clone();
         let last_key = block_meta.last().unwrap().last_key.clone();
 
@@ -164,6 +343,7 @@ impl SsTable {
             last_key,
             bloom: Some(bloom),
             max_ts: u64::MAX,
+            compression,
         })
     }
 
@@ -175,7 +355,7 @@ impl SsTable {
         last_key: KeyBytes,
     ) -> Self {
         Self {
-            file: FileObject(None, file_size),
+            file: FileObject(None, file_size, None),
             block_meta: vec![],
             block_meta_offset: 0,
             id,
@@ -184,6 +364,7 @@ impl SsTable {
             last_key,
             bloom: None,
             max_ts: 0,
+            compression: CompressionType::None,
         }
     }
 
@@ -195,8 +376,28 @@ impl SsTable {
             None => self.block_meta_offset,
         };
         let block_len = (next_offset - offset) as u64;
-        let block_buffer = self.file.read(offset as u64, block_len)?;
-        let block = Block::decode(&block_buffer);
+        // Prefer a zero-copy borrow from the memory map; fall back to a pread for
+        // files opened via `create`/`open`.
+        let owned;
+        let block_buffer: &[u8] = match self.file.read_slice(offset as u64, block_len) {
+            Ok(slice) => slice,
+            Err(_) => {
+                owned = self.file.read(offset as u64, block_len)?;
+                &owned
+            }
+        };
+        // The trailing 4 bytes are an xxh3 checksum over the on-disk block bytes
+        // (compressed, if compression is enabled); verify before decoding.
+        let block_bytes = verify_checksum(
+            block_buffer,
+            format_args!("block (sst_id={}, block_idx={block_idx})", self.id),
+        )?;
+        // Fast path for uncompressed tables: decode straight from the borrowed
+        // slice, avoiding the decompressor's allocation and copy.
+        let block = match self.compression {
+            CompressionType::None => Block::decode(block_bytes),
+            compression => Block::decode(&compression.decompress(block_bytes)?),
+        };
         Ok(Arc::new(block))
     }
 
@@ -223,6 +424,19 @@ impl SsTable {
             .unwrap_err()
     }
 
+    /// Quick bloom-filter membership test used to short-circuit point reads.
+    ///
+    /// Returns `false` only when the bloom filter proves `key` is absent, so the
+    /// `get_with_ts` path can skip this table entirely — no `find_block_idx`, no
+    /// block read, no cache lookup. Returns `true` when the key may be present or
+    /// when the table carries no bloom filter. The key is hashed with the same
+    /// `farmhash` fingerprint used when the filter was built.
+    pub fn may_contain(&self, key: KeySlice) -> bool {
+        self.bloom.as_ref().map_or(true, |bloom| {
+            bloom.may_contain(farmhash::fingerprint32(key.raw_ref()))
+        })
+    }
+
     /// Get number of data blocks.
     pub fn num_of_blocks(&self) -> usize {
         self.block_meta.len()